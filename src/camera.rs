@@ -0,0 +1,218 @@
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+
+use crate::controller::Player;
+use crate::UpDirection;
+
+/// Which system is allowed to drive the `Camera3d` transform this frame.
+#[derive(Resource, Default, PartialEq, Eq)]
+pub enum CameraMode {
+    /// First-person view locked to the player capsule's eye.
+    #[default]
+    FirstPerson,
+    /// Free-flying debug camera driven by [`FlyCamera`].
+    Fly,
+    /// Third-person camera that trails the player capsule.
+    Follow,
+}
+
+/// Key that cycles [`CameraMode::FirstPerson`] -> [`CameraMode::Fly`] ->
+/// [`CameraMode::Follow`] -> back to first person.
+const TOGGLE_KEY: KeyCode = KeyCode::C;
+
+/// Height of the first-person eye above the player origin, in meters.
+const EYE_HEIGHT: f32 = 1.5;
+/// Distance the follow camera trails behind the player, in meters.
+const CAM_DIST: f32 = 25.0;
+/// Height the follow camera sits above the player along its up axis.
+const CAM_HEIGHT: f32 = 10.0;
+/// Follow-camera position smoothing rate (higher snaps faster).
+const CAM_SMOOTH: f32 = 6.0;
+
+/// Free-flying debug camera, moved with WASD and steered with the mouse.
+#[derive(Component)]
+pub struct FlyCamera {
+    /// Acceleration applied while a movement key is held.
+    pub accel: f32,
+    /// Upper bound on fly speed.
+    pub max_speed: f32,
+    /// Mouse look sensitivity.
+    pub sensitivity: f32,
+    /// Velocity damping applied when no key is held.
+    pub friction: f32,
+    /// Accumulated pitch in degrees.
+    pub pitch: f32,
+    /// Accumulated yaw in degrees.
+    pub yaw: f32,
+    /// Current velocity, integrated each frame.
+    pub velocity: Vec3,
+}
+
+impl Default for FlyCamera {
+    fn default() -> Self {
+        Self {
+            accel: 1.5,
+            max_speed: 0.5,
+            sensitivity: 3.0,
+            friction: 1.0,
+            pitch: 0.0,
+            yaw: 0.0,
+            velocity: Vec3::ZERO,
+        }
+    }
+}
+
+/// Marks the camera as the third-person follow camera target consumer.
+#[derive(Component, Default)]
+pub struct FollowCamera;
+
+/// Registers the camera systems and the [`CameraMode`] toggle state.
+pub struct FlyCameraPlugin;
+
+impl Plugin for FlyCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraMode>()
+            .add_system(toggle_camera_mode)
+            .add_system(first_person_camera_system)
+            .add_system(camera_movement_system)
+            .add_system(mouse_motion_system)
+            .add_system(follow_camera_system);
+    }
+}
+
+/// Flips the active [`CameraMode`] when the toggle key is pressed.
+fn toggle_camera_mode(keyboard: Res<Input<KeyCode>>, mut mode: ResMut<CameraMode>) {
+    if keyboard.just_pressed(TOGGLE_KEY) {
+        *mode = match *mode {
+            CameraMode::FirstPerson => CameraMode::Fly,
+            CameraMode::Fly => CameraMode::Follow,
+            CameraMode::Follow => CameraMode::FirstPerson,
+        };
+    }
+}
+
+/// Locks the camera to the player's eye and facing while in first-person mode,
+/// giving the controller the FPS view the request asks for.
+fn first_person_camera_system(
+    mode: Res<CameraMode>,
+    players: Query<(&Transform, Option<&UpDirection>), (With<Player>, Without<FollowCamera>)>,
+    mut cameras: Query<&mut Transform, With<FollowCamera>>,
+) {
+    if *mode != CameraMode::FirstPerson {
+        return;
+    }
+    let Ok((player, up)) = players.get_single() else {
+        return;
+    };
+    let up = up
+        .map(|u| u.0)
+        .filter(|u| *u != Vec3::ZERO)
+        .unwrap_or(Vec3::Y);
+
+    let eye = player.translation + up * EYE_HEIGHT;
+    for mut camera in cameras.iter_mut() {
+        camera.translation = eye;
+        camera.look_at(eye + player.forward(), up);
+    }
+}
+
+/// Integrates WASD input into the fly camera's position while in fly mode.
+fn camera_movement_system(
+    time: Res<Time>,
+    mode: Res<CameraMode>,
+    keyboard: Res<Input<KeyCode>>,
+    mut query: Query<(&mut FlyCamera, &mut Transform)>,
+) {
+    if *mode != CameraMode::Fly {
+        return;
+    }
+    let dt = time.delta_seconds();
+    for (mut camera, mut transform) in query.iter_mut() {
+        let (forward, right, up) = (
+            transform.forward(),
+            transform.right(),
+            transform.up(),
+        );
+        let mut axis = Vec3::ZERO;
+        if keyboard.pressed(KeyCode::W) {
+            axis += forward;
+        }
+        if keyboard.pressed(KeyCode::S) {
+            axis -= forward;
+        }
+        if keyboard.pressed(KeyCode::D) {
+            axis += right;
+        }
+        if keyboard.pressed(KeyCode::A) {
+            axis -= right;
+        }
+        if keyboard.pressed(KeyCode::Space) {
+            axis += up;
+        }
+        if keyboard.pressed(KeyCode::LShift) {
+            axis -= up;
+        }
+
+        if axis.length_squared() > 0.0 {
+            camera.velocity += axis.normalize() * camera.accel * dt;
+            camera.velocity = camera.velocity.clamp_length_max(camera.max_speed);
+        } else {
+            let damp = (camera.friction * dt).min(1.0);
+            camera.velocity = camera.velocity.lerp(Vec3::ZERO, damp);
+        }
+        transform.translation += camera.velocity;
+    }
+}
+
+/// Applies mouse motion to the fly camera's yaw/pitch while in fly mode.
+fn mouse_motion_system(
+    time: Res<Time>,
+    mode: Res<CameraMode>,
+    mut motion: EventReader<MouseMotion>,
+    mut query: Query<(&mut FlyCamera, &mut Transform)>,
+) {
+    if *mode != CameraMode::Fly {
+        motion.clear();
+        return;
+    }
+    let dt = time.delta_seconds();
+    let mut delta = Vec2::ZERO;
+    for event in motion.iter() {
+        delta += event.delta;
+    }
+    for (mut camera, mut transform) in query.iter_mut() {
+        camera.yaw -= delta.x * camera.sensitivity * dt;
+        camera.pitch += delta.y * camera.sensitivity * dt;
+        camera.pitch = camera.pitch.clamp(-89.0, 89.0);
+
+        transform.rotation = Quat::from_axis_angle(Vec3::Y, camera.yaw.to_radians())
+            * Quat::from_axis_angle(Vec3::X, -camera.pitch.to_radians());
+    }
+}
+
+/// Trails the player capsule while in follow mode, orienting to the player's
+/// up direction so it also works under curved/planet gravity.
+fn follow_camera_system(
+    time: Res<Time>,
+    mode: Res<CameraMode>,
+    players: Query<(&Transform, Option<&UpDirection>), (With<Player>, Without<FollowCamera>)>,
+    mut cameras: Query<&mut Transform, With<FollowCamera>>,
+) {
+    if *mode != CameraMode::Follow {
+        return;
+    }
+    let Ok((player, up)) = players.get_single() else {
+        return;
+    };
+    let up = up
+        .map(|u| u.0)
+        .filter(|u| *u != Vec3::ZERO)
+        .unwrap_or(Vec3::Y);
+
+    let target = player.translation + player.back() * CAM_DIST + up * CAM_HEIGHT;
+    let blend = (CAM_SMOOTH * time.delta_seconds()).min(1.0);
+    for mut camera in cameras.iter_mut() {
+        camera.translation = camera.translation.lerp(target, blend);
+        camera.look_at(player.translation, up);
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::camera::{FlyCamera, FollowCamera};
+use crate::UpDirection;
+
+/// Tunables for the first-person walking controller.
+///
+/// Registered as a reflected resource so reflection-based tools can edit it
+/// at runtime; the app itself adds no inspector.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct MovementSettings {
+    /// Horizontal acceleration applied while WASD is held, in m/s².
+    pub accel: f32,
+    /// Upper bound on horizontal speed, in m/s.
+    pub max_speed: f32,
+    /// Downward acceleration integrated into vertical velocity, in m/s².
+    pub gravity: f32,
+    /// Vertical velocity set on jump while grounded, in m/s.
+    pub jump_impulse: f32,
+    /// Fraction of `accel` that still applies while airborne (0..=1).
+    pub air_control: f32,
+    /// Mouse-look sensitivity, in radians per pixel.
+    pub mouse_sensitivity: f32,
+}
+
+impl Default for MovementSettings {
+    fn default() -> Self {
+        Self {
+            accel: 60.0,
+            max_speed: 20.0,
+            gravity: 98.1,
+            jump_impulse: 30.0,
+            air_control: 0.3,
+            mouse_sensitivity: 0.00012,
+        }
+    }
+}
+
+/// Marks the kinematic capsule driven by [`move_controller`] and carries the
+/// controller's own velocity state between frames.
+#[derive(Component, Default)]
+pub struct Player {
+    /// Persisted velocity integrated by the controller each tick.
+    pub velocity: Vec3,
+}
+
+/// Spawns the player capsule with a [`KinematicCharacterController`] plus a
+/// standalone camera that the fly/follow systems can drive.
+pub fn setup_controller(mut commands: Commands) {
+    commands
+        .spawn(TransformBundle::from(Transform::from_xyz(
+            -30.0, 30.0, 50.0,
+        )))
+        .insert(Collider::capsule(Vec3::Y * 0.5, Vec3::Y * 1.5, 0.5))
+        .insert(ActiveEvents::COLLISION_EVENTS)
+        .insert(RigidBody::KinematicPositionBased)
+        .insert(KinematicCharacterController {
+            // Leave a small skin so the controller keeps reporting `grounded`.
+            offset: CharacterLength::Absolute(0.01),
+            ..Default::default()
+        })
+        .insert(Player::default());
+
+    // One camera entity, toggled between the fly and follow systems at runtime.
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_xyz(-30.0, 30.0, 100.0)
+                .looking_at(Vec3::new(0.0, 10.0, 0.0), Vec3::Y),
+            ..Default::default()
+        })
+        .insert(FlyCamera::default())
+        .insert(FollowCamera);
+}
+
+/// Reads WASD + space and feeds the accumulated motion through the Rapier
+/// [`KinematicCharacterController`] each frame.
+pub fn move_controller(
+    time: Res<Time>,
+    settings: Res<MovementSettings>,
+    keyboard: Res<Input<KeyCode>>,
+    mut motion: EventReader<MouseMotion>,
+    mut players: Query<(
+        &mut Player,
+        &mut KinematicCharacterController,
+        &mut Transform,
+        Option<&KinematicCharacterControllerOutput>,
+        Option<&UpDirection>,
+    )>,
+) {
+    let dt = time.delta_seconds();
+    if dt == 0.0 {
+        return;
+    }
+
+    // Accumulate this frame's horizontal mouse motion into a yaw delta.
+    let mut yaw_delta = 0.0;
+    for event in motion.iter() {
+        yaw_delta -= event.delta.x * settings.mouse_sensitivity;
+    }
+
+    for (mut player, mut controller, mut transform, output, up) in players.iter_mut() {
+        let grounded = output.map(|o| o.grounded).unwrap_or(false);
+
+        // "Up" follows the gravity source so the avatar walks on curved worlds;
+        // fall back to world up before `apply_gravity` has published one.
+        let up = up
+            .map(|u| u.0)
+            .filter(|u| *u != Vec3::ZERO)
+            .unwrap_or(Vec3::Y);
+
+        // Steer the capsule with the mouse so WASD follows where it faces,
+        // yawing about the current up axis.
+        transform.rotate_axis(up, yaw_delta);
+
+        // Build the desired move direction and flatten it onto the tangent
+        // plane perpendicular to `up`.
+        let forward = transform.forward();
+        let right = transform.right();
+        let mut wish = Vec3::ZERO;
+        if keyboard.pressed(KeyCode::W) {
+            wish += forward;
+        }
+        if keyboard.pressed(KeyCode::S) {
+            wish -= forward;
+        }
+        if keyboard.pressed(KeyCode::D) {
+            wish += right;
+        }
+        if keyboard.pressed(KeyCode::A) {
+            wish -= right;
+        }
+        wish -= up * wish.dot(up);
+
+        // Split the stored velocity into its along-up and tangential parts.
+        let mut vertical = player.velocity.dot(up);
+        let mut horizontal = player.velocity - up * vertical;
+
+        // Accelerate tangential velocity toward `max_speed`, damping it when no
+        // input is held on the ground. Airborne input is scaled by `air_control`.
+        let control = if grounded { 1.0 } else { settings.air_control };
+        if wish.length_squared() > 0.0 {
+            horizontal += wish.normalize() * settings.accel * control * dt;
+            horizontal = horizontal.clamp_length_max(settings.max_speed);
+        } else if grounded {
+            horizontal = horizontal.lerp(Vec3::ZERO, (settings.accel * dt).min(1.0));
+        }
+
+        // Integrate along-up velocity: reset on the ground, jump on space, then
+        // apply gravity (toward the source) every frame, grounded or not.
+        if grounded && vertical < 0.0 {
+            vertical = 0.0;
+        }
+        if grounded && keyboard.just_pressed(KeyCode::Space) {
+            vertical = settings.jump_impulse;
+        }
+        vertical -= settings.gravity * dt;
+
+        player.velocity = horizontal + up * vertical;
+        controller.translation = Some(player.velocity * dt);
+    }
+}
@@ -3,7 +3,10 @@ use bevy_rapier3d::prelude::*;
 
 mod camera;
 mod controller;
+mod terrain;
 use crate::camera::*;
+use crate::controller::*;
+use crate::terrain::*;
 
 fn main() {
     App::new()
@@ -17,42 +20,29 @@ fn main() {
         .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
         .add_plugin(RapierDebugRenderPlugin::default())
         .add_plugin(FlyCameraPlugin)
-        .add_startup_system(setup_graphics)
+        .init_resource::<MovementSettings>()
+        .register_type::<MovementSettings>()
+        .init_resource::<TunnelingSettings>()
+        .init_resource::<TerrainSettings>()
         .add_startup_system(setup_physics)
+        .add_startup_system(setup_terrain)
+        .add_startup_system(setup_controller)
+        .add_system(move_controller)
         .add_system(cast_shape)
+        .add_system(anti_tunneling)
         .add_system(collision_events)
+        .add_system(despawn_lifetimes)
+        .init_resource::<GravitySource>()
+        .add_system(apply_gravity)
         .insert_resource(RapierConfiguration {
-            gravity: Vec3::Y * -98.1,
+            // Global gravity is zeroed; `apply_gravity` drives per-body forces.
+            gravity: Vec3::ZERO,
             ..Default::default()
         })
         .run();
 }
 
-fn setup_graphics(mut commands: Commands) {
-    commands
-        .spawn(Camera3dBundle {
-            transform: Transform::from_xyz(-30.0, 30.0, 100.0)
-                .looking_at(Vec3::new(0.0, 10.0, 0.0), Vec3::Y),
-            ..Default::default()
-        })
-        .insert(FlyCamera::default());
-}
-
 pub fn setup_physics(mut commands: Commands) {
-    /*
-     * Ground
-     */
-    let ground_size = 200.1;
-    let ground_height = 0.1;
-
-    commands
-        .spawn(TransformBundle::from(Transform::from_xyz(
-            0.0,
-            -ground_height,
-            0.0,
-        )))
-        .insert(Collider::cuboid(ground_size, ground_height, ground_size));
-
     /*
      * Create the cubes
      */
@@ -83,21 +73,75 @@ pub fn setup_physics(mut commands: Commands) {
 
         offset -= 0.05 * rad * (num as f32 - 1.0);
     }
+}
 
-    // Insert player
-    commands
-        .spawn(TransformBundle::from(Transform::from_xyz(
-            -30.0, 30.0, 50.0,
-        )))
-        .insert(Collider::capsule(Vec3::Y * 0.5, Vec3::Y * 1.5, 0.5))
-        .insert(ActiveEvents::COLLISION_EVENTS)
-        .insert(Velocity::zero())
-        .insert(RigidBody::Dynamic)
-        .insert(Sleeping::disabled())
-        .insert(LockedAxes::ROTATION_LOCKED)
-        .insert(AdditionalMassProperties::Mass(1.0))
-        .insert(GravityScale(1.0))
-        .insert(Ccd { enabled: true });
+/// A point every body falls toward, replacing the global directional gravity.
+#[derive(Resource)]
+pub struct GravitySource {
+    pub center: Vec3,
+    pub strength: f32,
+}
+
+impl Default for GravitySource {
+    fn default() -> Self {
+        Self {
+            // Far below the terrain so the default demo pulls bodies nearly
+            // straight down; move the center near a body to get planet gravity.
+            center: Vec3::Y * -100_000.0,
+            strength: 98.1,
+        }
+    }
+}
+
+/// The "up" direction away from the gravity center, kept on each body so the
+/// controller and camera can orient to a curved surface.
+#[derive(Component, Default)]
+pub struct UpDirection(pub Vec3);
+
+/* Pull every dynamic body toward the gravity source instead of straight down. */
+fn apply_gravity(
+    mut commands: Commands,
+    source: Res<GravitySource>,
+    mut config: ResMut<RapierConfiguration>,
+    mut bodies: Query<(
+        Entity,
+        &Transform,
+        &RigidBody,
+        Option<&mut ExternalForce>,
+        Option<&ReadMassProperties>,
+        Option<&mut UpDirection>,
+    )>,
+) {
+    // Keep the global setting inert; all gravity comes from per-body forces.
+    config.gravity = Vec3::ZERO;
+
+    for (entity, transform, body, force, mass, up) in bodies.iter_mut() {
+        let up_dir = (transform.translation - source.center).normalize_or_zero();
+        match up {
+            Some(mut up) => up.0 = up_dir,
+            None => {
+                commands.entity(entity).insert(UpDirection(up_dir));
+            }
+        }
+
+        // Only dynamic bodies are force-driven; the kinematic player integrates
+        // its own gravity inside the controller.
+        if !matches!(body, RigidBody::Dynamic) {
+            continue;
+        }
+
+        let mass = mass.map(|m| m.0.mass).unwrap_or(1.0);
+        let pull = -up_dir * source.strength * mass;
+        match force {
+            Some(mut force) => force.force = pull,
+            None => {
+                commands.entity(entity).insert(ExternalForce {
+                    force: pull,
+                    ..Default::default()
+                });
+            }
+        }
+    }
 }
 
 /* Cast a shape inside of a system. */
@@ -131,17 +175,211 @@ fn cast_shape(
                 .insert(Sleeping::disabled())
                 .insert(Ccd::enabled())
                 .insert(shape.clone())
+                .insert(PreviousVelocity::default())
+                .insert(Explosive {
+                    radius: 20.0,
+                    strength: 8000.0,
+                })
                 .insert(ActiveEvents::COLLISION_EVENTS);
         }
     }
 }
 
+/// Thresholds for the anti-tunneling recovery pass.
+#[derive(Resource)]
+pub struct TunnelingSettings {
+    /// Speed above which a body is shape-cast for missed collisions, in m/s.
+    pub speed_threshold: f32,
+    /// How many frames a recovered body's re-penetration is clamped.
+    pub frames: usize,
+}
+
+impl Default for TunnelingSettings {
+    fn default() -> Self {
+        Self {
+            speed_threshold: 50.0,
+            frames: 15,
+        }
+    }
+}
+
+/// Last frame's velocity, cached by [`anti_tunneling`] to reconstruct the swept
+/// path for the shape-cast.
+#[derive(Component, Default)]
+pub struct PreviousVelocity(pub Velocity);
+
+/// Tags a body that was snapped back after a missed collision; its motion along
+/// `dir` stays clamped for `frames` ticks while the solver stabilizes.
+#[derive(Component)]
+pub struct Tunneling {
+    pub frames: usize,
+    pub dir: Vec3,
+}
+
+/* Catch fast bodies that tunnel through thin geometry despite CCD. */
+fn anti_tunneling(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<TunnelingSettings>,
+    rapier_context: Res<RapierContext>,
+    mut bodies: Query<(
+        Entity,
+        &mut Transform,
+        &mut Velocity,
+        &mut PreviousVelocity,
+        &Collider,
+        Option<&mut Tunneling>,
+    )>,
+) {
+    let dt = time.delta_seconds();
+
+    for (entity, mut transform, mut velocity, mut previous, collider, tunneling) in
+        bodies.iter_mut()
+    {
+        // Run down an active recovery: clamp any velocity heading back into the
+        // surface, and drop the tag once the countdown reaches zero.
+        if let Some(mut tunneling) = tunneling {
+            let into = velocity.linvel.dot(tunneling.dir);
+            if into < 0.0 {
+                velocity.linvel -= tunneling.dir * into;
+            }
+            tunneling.frames = tunneling.frames.saturating_sub(1);
+            if tunneling.frames == 0 {
+                commands.entity(entity).remove::<Tunneling>();
+            }
+        }
+
+        // Only sweep bodies that are moving fast enough to skip a contact.
+        if velocity.linvel.length() > settings.speed_threshold {
+            let prev_vel = previous.0.linvel;
+            let travel = prev_vel * dt;
+            let distance = travel.length();
+            if distance > 0.0 {
+                let origin = transform.translation - travel;
+                let direction = prev_vel / distance;
+                let filter = QueryFilter::default().exclude_collider(entity);
+                if let Some((_, hit)) = rapier_context.cast_shape(
+                    origin,
+                    transform.rotation,
+                    direction,
+                    collider,
+                    distance,
+                    filter,
+                ) {
+                    // `hit.toi` is now in meters along `direction`.
+                    if hit.toi < distance {
+                        transform.translation = origin + direction * hit.toi;
+                        let normal = hit.normal1;
+                        let into = velocity.linvel.dot(normal);
+                        if into < 0.0 {
+                            velocity.linvel -= normal * into;
+                        }
+                        commands.entity(entity).insert(Tunneling {
+                            frames: settings.frames,
+                            dir: normal,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Cache this frame's velocity for next frame's sweep.
+        previous.0 = *velocity;
+    }
+}
+
+/// Tags a projectile that detonates with a radial impulse on its first contact.
+#[derive(Component)]
+pub struct Explosive {
+    /// Blast radius of the impulse query, in meters.
+    pub radius: f32,
+    /// Impulse magnitude at the blast center, falling off linearly to zero.
+    pub strength: f32,
+}
+
+/// Despawns an entity once its timer elapses; used for the blast marker sphere.
+#[derive(Component)]
+pub struct Lifetime(pub Timer);
+
 /* A system that compute collision events. */
-fn collision_events(mut commands: Commands, mut collision_events: EventReader<CollisionEvent>) {
+fn collision_events(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    rapier_context: Res<RapierContext>,
+    explosives: Query<&Explosive>,
+    transforms: Query<&Transform>,
+    rigid_bodies: Query<&RigidBody>,
+    mut collision_events: EventReader<CollisionEvent>,
+) {
     for collision_event in collision_events.iter() {
-        println!("Received collision event: {:?}", collision_event);
-
         if let CollisionEvent::Started(entity1, entity2, _) = collision_event {
+            // Detonate any explosive projectile involved in the contact.
+            let mut detonated = false;
+            for entity in [*entity1, *entity2] {
+                let Ok(explosive) = explosives.get(entity) else {
+                    continue;
+                };
+                let Ok(transform) = transforms.get(entity) else {
+                    continue;
+                };
+                let center = transform.translation;
+
+                // Push every dynamic body inside the blast radius away from the
+                // center, scaled by a linear distance falloff.
+                rapier_context.intersections_with_shape(
+                    center,
+                    Quat::IDENTITY,
+                    &Collider::ball(explosive.radius),
+                    QueryFilter::default(),
+                    |hit| {
+                        if hit == entity || !matches!(rigid_bodies.get(hit), Ok(RigidBody::Dynamic))
+                        {
+                            return true;
+                        }
+                        if let Ok(hit_transform) = transforms.get(hit) {
+                            let offset = hit_transform.translation - center;
+                            let dist = offset.length();
+                            if dist < explosive.radius {
+                                let falloff = 1.0 - dist / explosive.radius;
+                                let dir = offset.normalize_or_zero();
+                                commands.entity(hit).insert(ExternalImpulse {
+                                    impulse: dir * explosive.strength * falloff,
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                        true
+                    },
+                );
+
+                // Debug-colored marker so the blast is visible for a moment.
+                commands
+                    .spawn(PbrBundle {
+                        mesh: meshes.add(
+                            Mesh::try_from(shape::Icosphere {
+                                radius: explosive.radius,
+                                subdivisions: 3,
+                            })
+                            .unwrap(),
+                        ),
+                        material: materials.add(StandardMaterial {
+                            base_color: Color::rgba(1.0, 0.4, 0.0, 0.3),
+                            alpha_mode: AlphaMode::Blend,
+                            ..Default::default()
+                        }),
+                        transform: Transform::from_translation(center),
+                        ..Default::default()
+                    })
+                    .insert(Lifetime(Timer::from_seconds(0.3, TimerMode::Once)));
+
+                commands.entity(entity).despawn();
+                detonated = true;
+            }
+            if detonated {
+                continue;
+            }
+
             let color = Color::YELLOW;
             commands
                 .entity(entity1.clone())
@@ -163,6 +401,19 @@ fn collision_events(mut commands: Commands, mut collision_events: EventReader<Co
     }
 }
 
+/* Despawn short-lived entities (e.g. explosion markers) once they expire. */
+fn despawn_lifetimes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lifetimes: Query<(Entity, &mut Lifetime)>,
+) {
+    for (entity, mut lifetime) in lifetimes.iter_mut() {
+        if lifetime.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
 // Credit to @doomy on discord.
 fn ray_from_mouse_position(
     window: &Window,
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy_rapier3d::prelude::*;
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+
+/// Parameters for the procedurally generated heightfield terrain.
+#[derive(Resource)]
+pub struct TerrainSettings {
+    /// World-space extent of the terrain along X and Z, in meters.
+    pub size: f32,
+    /// Number of samples per side; the grid has `resolution`² vertices.
+    pub resolution: usize,
+    /// Seed fed to the fractal noise so a landscape can be reproduced.
+    pub seed: u32,
+    /// Number of fractal octaves layered into the height.
+    pub octaves: usize,
+    /// Base frequency of the lowest noise octave.
+    pub frequency: f64,
+    /// Peak height of the generated surface, in meters.
+    pub amplitude: f32,
+}
+
+impl Default for TerrainSettings {
+    fn default() -> Self {
+        Self {
+            size: 400.0,
+            resolution: 129,
+            seed: 0,
+            octaves: 5,
+            frequency: 1.0 / 120.0,
+            amplitude: 30.0,
+        }
+    }
+}
+
+/// Generates the terrain mesh and a matching heightfield collider from layered
+/// Perlin noise, replacing the flat cuboid ground.
+pub fn setup_terrain(
+    mut commands: Commands,
+    settings: Res<TerrainSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let res = settings.resolution.max(2);
+    let noise = Fbm::<Perlin>::new(settings.seed)
+        .set_octaves(settings.octaves)
+        .set_frequency(settings.frequency);
+
+    // Sample the fractal over the grid; `heights` is row-major (i over X, j
+    // over Z) and shared by both the render mesh and the collider.
+    let mut heights = vec![0.0f32; res * res];
+    for i in 0..res {
+        for j in 0..res {
+            let x = (i as f32 / (res - 1) as f32 - 0.5) * settings.size;
+            let z = (j as f32 / (res - 1) as f32 - 0.5) * settings.size;
+            let sample = noise.get([x as f64, z as f64]) as f32;
+            heights[i * res + j] = sample * settings.amplitude;
+        }
+    }
+
+    // Build the render mesh: one vertex per sample, two triangles per cell.
+    let mut positions = Vec::with_capacity(res * res);
+    let mut uvs = Vec::with_capacity(res * res);
+    for i in 0..res {
+        for j in 0..res {
+            let x = (i as f32 / (res - 1) as f32 - 0.5) * settings.size;
+            let z = (j as f32 / (res - 1) as f32 - 0.5) * settings.size;
+            positions.push([x, heights[i * res + j], z]);
+            uvs.push([i as f32 / (res - 1) as f32, j as f32 / (res - 1) as f32]);
+        }
+    }
+
+    let mut indices = Vec::with_capacity((res - 1) * (res - 1) * 6);
+    for i in 0..res - 1 {
+        for j in 0..res - 1 {
+            let a = (i * res + j) as u32;
+            let b = (i * res + j + 1) as u32;
+            let c = ((i + 1) * res + j) as u32;
+            let d = ((i + 1) * res + j + 1) as u32;
+            indices.extend_from_slice(&[a, c, b, b, c, d]);
+        }
+    }
+
+    // Smooth normals accumulated from each adjacent face.
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for tri in indices.chunks_exact(3) {
+        let (ia, ib, ic) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+        let pa = Vec3::from(positions[ia]);
+        let pb = Vec3::from(positions[ib]);
+        let pc = Vec3::from(positions[ic]);
+        let face = (pb - pa).cross(pc - pa);
+        normals[ia] += face;
+        normals[ib] += face;
+        normals[ic] += face;
+    }
+    let normals: Vec<[f32; 3]> = normals
+        .into_iter()
+        .map(|n| n.normalize_or_zero().to_array())
+        .collect();
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+
+    // Heightfield collider from the same samples, centered on the origin.
+    // parry reshapes the flat `Vec` into rows→Z, cols→X; our `i→X, j→Z`
+    // row-major fill already lands each sample at its matching (x, z), so the
+    // collider lines up with the drawn surface without transposing.
+    let collider = Collider::heightfield(
+        heights,
+        res,
+        res,
+        Vec3::new(settings.size, 1.0, settings.size),
+    );
+
+    commands
+        .spawn(PbrBundle {
+            mesh: meshes.add(mesh),
+            material: materials.add(Color::rgb(0.4, 0.55, 0.3).into()),
+            ..Default::default()
+        })
+        .insert(collider);
+}